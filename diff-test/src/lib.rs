@@ -1,9 +1,14 @@
 use alloy::{primitives::U256, sol};
+use ark_bn254::{Fq, Fq2, Fr, G1Affine, G1Projective, G2Affine};
 use ark_ec::{
     short_weierstrass::{Affine, SWCurveConfig},
-    AffineRepr,
+    AffineRepr, CurveGroup, Group,
 };
-use ark_ff::{BigInteger, Fp2, Fp2Config, PrimeField};
+use ark_ff::{BigInteger, Field, Fp2, Fp2Config, PrimeField, Zero};
+use ark_serialize::{
+    CanonicalDeserialize, CanonicalSerialize, Compress, SerializationError, Valid, Validate,
+};
+use ark_std::io::{Read, Write};
 
 // TODO: (alex) maybe move these commonly shared util to a crate
 /// convert a field element to U256, panic if field size is larger than 256 bit
@@ -22,10 +27,12 @@ pub fn u256_to_field<F: PrimeField>(x: U256) -> F {
 
 // same as `forge bind --alloy`, only the struct related part
 sol! {
+    #[derive(serde::Serialize, serde::Deserialize)]
     struct G1Point {
         uint256 x;
         uint256 y;
     }
+    #[derive(serde::Serialize, serde::Deserialize)]
     struct G2Point {
         uint256 x0;
         uint256 x1;
@@ -85,6 +92,59 @@ where
     }
 }
 
+/// Errors that can occur when validating a Solidity-side point (`G1Point`/
+/// `G2Point`) while converting it into an arkworks `Affine` point.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+pub enum ConversionError {
+    /// a coordinate is not the canonical representative of a field element,
+    /// i.e. it's >= the field modulus
+    #[error("coordinate is not a canonical field element (>= modulus)")]
+    NonCanonicalCoordinate,
+    /// the point does not satisfy the curve equation
+    #[error("point is not on the curve")]
+    NotOnCurve,
+    /// the point is on curve, but not in the prime-order subgroup
+    #[error("point is not in the correct prime-order subgroup")]
+    NotInSubgroup,
+}
+
+/// like [`u256_to_field`], but rejects non-canonical encodings (>= the field
+/// modulus) instead of silently reducing them mod the field order.
+pub(crate) fn u256_to_field_checked<F: PrimeField>(x: U256) -> Option<F> {
+    let f: F = u256_to_field(x);
+    if field_to_u256(f) == x {
+        Some(f)
+    } else {
+        None
+    }
+}
+
+impl<P: SWCurveConfig> TryFrom<G1Point> for Affine<P>
+where
+    P::BaseField: PrimeField,
+{
+    type Error = ConversionError;
+
+    /// Validated conversion: rejects non-canonical coordinates and points
+    /// that don't satisfy the curve equation. G1 on BN254 has cofactor 1, so
+    /// on-curve is equivalent to subgroup membership and no further check is
+    /// needed.
+    fn try_from(p: G1Point) -> Result<Self, Self::Error> {
+        if p == G1Point::default() {
+            return Ok(Self::default());
+        }
+        let x = u256_to_field_checked::<P::BaseField>(p.x)
+            .ok_or(ConversionError::NonCanonicalCoordinate)?;
+        let y = u256_to_field_checked::<P::BaseField>(p.y)
+            .ok_or(ConversionError::NonCanonicalCoordinate)?;
+        let point = Self::new_unchecked(x, y);
+        if !point.is_on_curve() {
+            return Err(ConversionError::NotOnCurve);
+        }
+        Ok(point)
+    }
+}
+
 impl<P: SWCurveConfig<BaseField = Fp2<C>>, C> From<G2Point> for Affine<P>
 where
     C: Fp2Config,
@@ -97,6 +157,38 @@ where
     }
 }
 
+impl<P: SWCurveConfig<BaseField = Fp2<C>>, C> TryFrom<G2Point> for Affine<P>
+where
+    C: Fp2Config,
+{
+    type Error = ConversionError;
+
+    /// Validated conversion: rejects non-canonical coordinates, points that
+    /// don't satisfy the curve equation, and (since G2 has a nontrivial
+    /// cofactor) points outside the prime-order subgroup. The subgroup check
+    /// defers to `P::is_in_correct_subgroup_assuming_on_curve`, which for
+    /// BN254's G2 uses the untwist-Frobenius-twist endomorphism test rather
+    /// than a plain `[r]P == O` scalar multiplication.
+    fn try_from(p: G2Point) -> Result<Self, Self::Error> {
+        let x0 =
+            u256_to_field_checked::<C::Fp>(p.x0).ok_or(ConversionError::NonCanonicalCoordinate)?;
+        let x1 =
+            u256_to_field_checked::<C::Fp>(p.x1).ok_or(ConversionError::NonCanonicalCoordinate)?;
+        let y0 =
+            u256_to_field_checked::<C::Fp>(p.y0).ok_or(ConversionError::NonCanonicalCoordinate)?;
+        let y1 =
+            u256_to_field_checked::<C::Fp>(p.y1).ok_or(ConversionError::NonCanonicalCoordinate)?;
+        let point = Self::new_unchecked(Fp2::new(x0, x1), Fp2::new(y0, y1));
+        if !point.is_on_curve() {
+            return Err(ConversionError::NotOnCurve);
+        }
+        if !point.is_in_correct_subgroup_assuming_on_curve() {
+            return Err(ConversionError::NotInSubgroup);
+        }
+        Ok(point)
+    }
+}
+
 impl<P: SWCurveConfig<BaseField = Fp2<C>>, C> From<Affine<P>> for G2Point
 where
     C: Fp2Config,
@@ -110,3 +202,455 @@ where
         }
     }
 }
+
+// BN254's Fq is 254-bit, so a 32-byte big-endian encoding of an `Fq` element
+// has two spare high bits that would otherwise always be zero. We repurpose
+// them as flags when compressing a point down to a single coordinate.
+const COMPRESSED_INFINITY_FLAG: u8 = 0x80;
+const COMPRESSED_SIGN_FLAG: u8 = 0x40;
+const COMPRESSED_FLAG_MASK: u8 = COMPRESSED_INFINITY_FLAG | COMPRESSED_SIGN_FLAG;
+
+fn fq_to_be_bytes(f: Fq) -> [u8; 32] {
+    let mut bytes = [0u8; 32];
+    bytes.copy_from_slice(&f.into_bigint().to_bytes_be());
+    bytes
+}
+
+/// true iff `f` is the non-canonical (i.e. larger) of `{f, -f}`, mirroring
+/// the canonical-sqrt convention used by `Bn254Qr`.
+fn is_larger_root(f: Fq) -> bool {
+    f.into_bigint() > <Fq as PrimeField>::MODULUS_MINUS_ONE_DIV_TWO
+}
+
+/// Compress a G1 point to 32 bytes: the x-coordinate in big-endian, with the
+/// top two bits repurposed as an "infinity" flag and the sign of y (set when
+/// y is the non-canonical, larger root of `y^2 = x^3 + 3`). Infinity is
+/// encoded as an all-zero x with the infinity flag set.
+pub fn compress_g1(p: G1Affine) -> [u8; 32] {
+    if p.is_zero() {
+        let mut bytes = [0u8; 32];
+        bytes[0] |= COMPRESSED_INFINITY_FLAG;
+        return bytes;
+    }
+    let mut bytes = fq_to_be_bytes(p.x);
+    if is_larger_root(p.y) {
+        bytes[0] |= COMPRESSED_SIGN_FLAG;
+    }
+    bytes
+}
+
+/// Decompress a G1 point produced by [`compress_g1`].
+pub fn decompress_g1(bytes: [u8; 32]) -> G1Affine {
+    let infinity = bytes[0] & COMPRESSED_INFINITY_FLAG != 0;
+    if infinity {
+        return G1Affine::zero();
+    }
+    let sign = bytes[0] & COMPRESSED_SIGN_FLAG != 0;
+    let mut x_bytes = bytes;
+    x_bytes[0] &= !COMPRESSED_FLAG_MASK;
+    let x = Fq::from_be_bytes_mod_order(&x_bytes);
+
+    // y^2 = x^3 + b, b = 3 for BN254
+    let y2 = x * x * x + Fq::from(3u64);
+    let y = y2.sqrt().expect("decompress_g1: x is not on curve");
+    // always start from the canonical (smaller) root, then negate to match
+    // the stored sign bit
+    let y = if is_larger_root(y) { -y } else { y };
+    let y = if sign { -y } else { y };
+    G1Affine::new_unchecked(x, y)
+}
+
+/// true iff `f` is the non-canonical root of `{f, -f}`, comparing
+/// lexicographically on `(c1, c0)` as is convention for Fq2.
+fn is_larger_root_fq2(f: Fq2) -> bool {
+    if f.c1.is_zero() {
+        is_larger_root(f.c0)
+    } else {
+        is_larger_root(f.c1)
+    }
+}
+
+/// Compress a G2 point to 64 bytes: `(x0, x1)` each big-endian, with the top
+/// two bits of the `x0` word repurposed as an "infinity" flag and the sign
+/// of y (set when y is the non-canonical root, compared lexicographically on
+/// `(c1, c0)`). Infinity is encoded as all-zero coordinates with the
+/// infinity flag set.
+pub fn compress_g2(p: G2Affine) -> [u8; 64] {
+    let mut bytes = [0u8; 64];
+    if p.is_zero() {
+        bytes[0] |= COMPRESSED_INFINITY_FLAG;
+        return bytes;
+    }
+    bytes[..32].copy_from_slice(&fq_to_be_bytes(p.x.c0));
+    bytes[32..].copy_from_slice(&fq_to_be_bytes(p.x.c1));
+    if is_larger_root_fq2(p.y) {
+        bytes[0] |= COMPRESSED_SIGN_FLAG;
+    }
+    bytes
+}
+
+/// Decompress a G2 point produced by [`compress_g2`].
+pub fn decompress_g2(bytes: [u8; 64]) -> G2Affine {
+    let infinity = bytes[0] & COMPRESSED_INFINITY_FLAG != 0;
+    if infinity {
+        return G2Affine::zero();
+    }
+    let sign = bytes[0] & COMPRESSED_SIGN_FLAG != 0;
+    let mut x0_bytes = [0u8; 32];
+    x0_bytes.copy_from_slice(&bytes[..32]);
+    x0_bytes[0] &= !COMPRESSED_FLAG_MASK;
+    let x1_bytes: [u8; 32] = bytes[32..].try_into().unwrap();
+
+    let x = Fq2::new(
+        Fq::from_be_bytes_mod_order(&x0_bytes),
+        Fq::from_be_bytes_mod_order(&x1_bytes),
+    );
+
+    // y^2 = x^3 + b', where b' = 3 / (9 + u) is the twist coefficient of
+    // BN254's G2
+    let y2 = x * x * x + <ark_bn254::g2::Config as SWCurveConfig>::COEFF_B;
+    let y = y2.sqrt().expect("decompress_g2: x is not on curve");
+    let y = if is_larger_root_fq2(y) { -y } else { y };
+    let y = if sign { -y } else { y };
+    G2Affine::new_unchecked(x, y)
+}
+
+/// Choose pippenger's window size `c` (in bits) from the number of bases,
+/// following the usual `ln(n)`-style heuristic.
+pub fn msm_window_bits(num_bases: usize) -> usize {
+    if num_bases < 32 {
+        3
+    } else {
+        ((num_bases as f64).ln().ceil() as usize).max(4)
+    }
+}
+
+/// Affine addition for a batch of independent pairs, sharing a single field
+/// inversion (via `ark_ff::batch_inversion`, i.e. Montgomery's trick) across
+/// the whole batch. The edge cases where either point is infinity or the
+/// pair sums to infinity (`a == -b`) are resolved without touching the
+/// shared inversion; a doubling (`a == b`) contributes its `2y` denominator
+/// to the shared inversion like any other pair.
+fn batch_add_affine(pairs: &[(G1Affine, G1Affine)]) -> Vec<G1Affine> {
+    enum Pending {
+        Done(G1Affine),
+        Slope { a: G1Affine, b: G1Affine, numer: Fq },
+    }
+
+    let mut pending = Vec::with_capacity(pairs.len());
+    let mut denoms = Vec::new();
+
+    for &(a, b) in pairs {
+        if a.is_zero() {
+            pending.push(Pending::Done(b));
+        } else if b.is_zero() {
+            pending.push(Pending::Done(a));
+        } else if a.x == b.x && a.y == -b.y {
+            pending.push(Pending::Done(G1Affine::zero()));
+        } else if a == b {
+            // doubling: slope = 3x^2 / 2y (COEFF_A = 0 for BN254's G1)
+            let numer = a.x.square() * Fq::from(3u64);
+            denoms.push(a.y.double());
+            pending.push(Pending::Slope { a, b, numer });
+        } else {
+            let numer = b.y - a.y;
+            denoms.push(b.x - a.x);
+            pending.push(Pending::Slope { a, b, numer });
+        }
+    }
+
+    ark_ff::fields::batch_inversion(&mut denoms);
+    let mut denoms = denoms.into_iter();
+
+    pending
+        .into_iter()
+        .map(|p| match p {
+            Pending::Done(p) => p,
+            Pending::Slope { a, b, numer } => {
+                let slope = numer * denoms.next().unwrap();
+                let x3 = slope.square() - a.x - b.x;
+                let y3 = slope * (a.x - x3) - a.y;
+                G1Affine::new_unchecked(x3, y3)
+            }
+        })
+        .collect()
+}
+
+/// Collapse each bucket's list of bases into a single sum, via repeated
+/// rounds of pairwise batch-affine-addition: every round pairs up whatever
+/// is left in each bucket and adds all those pairs, across every bucket in
+/// the window, through one shared [`batch_add_affine`] call.
+fn sum_buckets_batched(mut buckets: Vec<Vec<G1Affine>>) -> Vec<G1Affine> {
+    loop {
+        let mut pairs = Vec::new();
+        let mut next: Vec<Vec<G1Affine>> = vec![Vec::new(); buckets.len()];
+        let mut any_pair = false;
+
+        for (bucket, next) in buckets.iter().zip(next.iter_mut()) {
+            let mut chunks = bucket.chunks_exact(2);
+            for chunk in &mut chunks {
+                pairs.push((chunk[0], chunk[1]));
+                any_pair = true;
+            }
+            next.extend_from_slice(chunks.remainder());
+        }
+
+        if !any_pair {
+            break;
+        }
+
+        let mut sums = batch_add_affine(&pairs).into_iter();
+        for (bucket, next) in buckets.iter().zip(next.iter_mut()) {
+            for _ in 0..(bucket.len() / 2) {
+                next.push(sums.next().unwrap());
+            }
+        }
+        buckets = next;
+    }
+
+    buckets
+        .into_iter()
+        .map(|bucket| bucket.first().copied().unwrap_or_else(G1Affine::zero))
+        .collect()
+}
+
+/// Combine a window's bucket sums into the window total via the running-sum
+/// trick: `total = sum_i i * bucket_i` computed with `2 * (2^c - 1)`
+/// additions (no scalar multiplications) by folding from the
+/// highest-indexed bucket down.
+fn combine_window_buckets(bucket_sums: &[G1Affine]) -> G1Projective {
+    let mut running_sum = G1Projective::zero();
+    let mut total = G1Projective::zero();
+    for bucket in bucket_sums.iter().rev() {
+        running_sum += bucket;
+        total += running_sum;
+    }
+    total
+}
+
+/// Split a scalar into `num_windows` base-`2^c` digits, least-significant
+/// window first.
+fn scalar_digits(s: &Fr, c: usize, num_windows: usize) -> Vec<usize> {
+    let bits = s.into_bigint().to_bits_le();
+    (0..num_windows)
+        .map(|w| {
+            let mut digit = 0usize;
+            for i in 0..c {
+                if bits.get(w * c + i).copied().unwrap_or(false) {
+                    digit |= 1 << i;
+                }
+            }
+            digit
+        })
+        .collect()
+}
+
+/// Bucket-method (Pippenger) MSM with batched affine additions, exposing the
+/// window size `c` and per-window bucket sums (see
+/// [`pippenger_msm_with_window`]) so that an on-chain MSM implementation's
+/// intermediate accumulation state can be diffed against this reference.
+pub fn pippenger_msm(bases: &[G1Affine], scalars: &[Fr]) -> G1Affine {
+    pippenger_msm_with_window(bases, scalars, msm_window_bits(bases.len())).0
+}
+
+/// Like [`pippenger_msm`], but with an explicit window size `c`, also
+/// returning the bucket sums computed for each window (before the
+/// running-sum combination into the window total).
+///
+/// For each window, every base is assigned to the bucket indexed by its
+/// `c`-bit digit in that window (digit 0 contributes nothing); each bucket
+/// is then summed via [`sum_buckets_batched`], which shares one field
+/// inversion across every pending pairwise addition in a round. Window
+/// totals are combined from most- to least-significant with `c` doublings
+/// between each, matching the accumulation order an on-chain MSM would use.
+pub fn pippenger_msm_with_window(
+    bases: &[G1Affine],
+    scalars: &[Fr],
+    c: usize,
+) -> (G1Affine, Vec<Vec<G1Affine>>) {
+    assert_eq!(bases.len(), scalars.len());
+    assert!((1..32).contains(&c));
+
+    let scalar_bits = Fr::MODULUS_BIT_SIZE as usize;
+    let num_windows = scalar_bits.div_ceil(c);
+    let num_buckets = (1usize << c) - 1;
+
+    let digits: Vec<Vec<usize>> = scalars
+        .iter()
+        .map(|s| scalar_digits(s, c, num_windows))
+        .collect();
+
+    let mut per_window_bucket_sums = Vec::with_capacity(num_windows);
+    let mut acc = G1Projective::zero();
+
+    for w in (0..num_windows).rev() {
+        for _ in 0..c {
+            acc.double_in_place();
+        }
+
+        let mut buckets: Vec<Vec<G1Affine>> = vec![Vec::new(); num_buckets];
+        for (base, digit) in bases.iter().zip(digits.iter()) {
+            let digit = digit[w];
+            if digit != 0 {
+                buckets[digit - 1].push(*base);
+            }
+        }
+
+        let bucket_sums = sum_buckets_batched(buckets);
+        acc += combine_window_buckets(&bucket_sums);
+        per_window_bucket_sums.push(bucket_sums);
+    }
+
+    // windows were processed most-significant first; restore
+    // least-significant-first order to match `scalar_digits`
+    per_window_bucket_sums.reverse();
+
+    (acc.into_affine(), per_window_bucket_sums)
+}
+
+fn u256_to_be_bytes(x: U256) -> [u8; 32] {
+    x.to_be_bytes::<32>()
+}
+
+impl G1Point {
+    /// Canonical 64-byte big-endian encoding (32 bytes per coordinate),
+    /// matching the EVM word layout. This is a stable, round-trippable
+    /// format for persisting fixtures, separate from the abi-encoded hex
+    /// the CLI prints.
+    pub fn to_bytes(&self) -> [u8; 64] {
+        let mut bytes = [0u8; 64];
+        bytes[..32].copy_from_slice(&u256_to_be_bytes(self.x));
+        bytes[32..].copy_from_slice(&u256_to_be_bytes(self.y));
+        bytes
+    }
+
+    /// Parse the encoding produced by [`Self::to_bytes`]. Rejects
+    /// coordinates that aren't canonical BN254 base field elements (>= the
+    /// modulus) rather than silently reducing them, so fixtures can double
+    /// as negative-test inputs.
+    pub fn from_bytes(bytes: [u8; 64]) -> Result<Self, ConversionError> {
+        let x = U256::from_be_slice(&bytes[..32]);
+        let y = U256::from_be_slice(&bytes[32..]);
+        u256_to_field_checked::<Fq>(x).ok_or(ConversionError::NonCanonicalCoordinate)?;
+        u256_to_field_checked::<Fq>(y).ok_or(ConversionError::NonCanonicalCoordinate)?;
+        Ok(Self { x, y })
+    }
+}
+
+impl G2Point {
+    /// Canonical 128-byte big-endian encoding (32 bytes per coordinate),
+    /// matching the EVM word layout.
+    pub fn to_bytes(&self) -> [u8; 128] {
+        let mut bytes = [0u8; 128];
+        bytes[..32].copy_from_slice(&u256_to_be_bytes(self.x0));
+        bytes[32..64].copy_from_slice(&u256_to_be_bytes(self.x1));
+        bytes[64..96].copy_from_slice(&u256_to_be_bytes(self.y0));
+        bytes[96..].copy_from_slice(&u256_to_be_bytes(self.y1));
+        bytes
+    }
+
+    /// Parse the encoding produced by [`Self::to_bytes`], rejecting
+    /// non-canonical coordinates (see [`G1Point::from_bytes`]).
+    pub fn from_bytes(bytes: [u8; 128]) -> Result<Self, ConversionError> {
+        let x0 = U256::from_be_slice(&bytes[..32]);
+        let x1 = U256::from_be_slice(&bytes[32..64]);
+        let y0 = U256::from_be_slice(&bytes[64..96]);
+        let y1 = U256::from_be_slice(&bytes[96..]);
+        u256_to_field_checked::<Fq>(x0).ok_or(ConversionError::NonCanonicalCoordinate)?;
+        u256_to_field_checked::<Fq>(x1).ok_or(ConversionError::NonCanonicalCoordinate)?;
+        u256_to_field_checked::<Fq>(y0).ok_or(ConversionError::NonCanonicalCoordinate)?;
+        u256_to_field_checked::<Fq>(y1).ok_or(ConversionError::NonCanonicalCoordinate)?;
+        Ok(Self { x0, x1, y0, y1 })
+    }
+}
+
+// These let a point move between our own `to_bytes`/`from_bytes` encoding
+// and arkworks' `CanonicalSerialize` encoding directly on `G1Point`/
+// `G2Point`, reusing the validated `TryFrom`/`From` conversions to `Affine`
+// under the hood instead of requiring callers to go through `Affine`
+// themselves.
+impl Valid for G1Point {
+    fn check(&self) -> Result<(), SerializationError> {
+        G1Affine::try_from(G1Point {
+            x: self.x,
+            y: self.y,
+        })
+        .map(|_| ())
+        .map_err(|_| SerializationError::InvalidData)
+    }
+}
+
+impl CanonicalSerialize for G1Point {
+    fn serialize_with_mode<W: Write>(
+        &self,
+        writer: W,
+        compress: Compress,
+    ) -> Result<(), SerializationError> {
+        let affine = G1Affine::try_from(G1Point {
+            x: self.x,
+            y: self.y,
+        })
+        .map_err(|_| SerializationError::InvalidData)?;
+        affine.serialize_with_mode(writer, compress)
+    }
+
+    fn serialized_size(&self, compress: Compress) -> usize {
+        G1Affine::default().serialized_size(compress)
+    }
+}
+
+impl CanonicalDeserialize for G1Point {
+    fn deserialize_with_mode<R: Read>(
+        reader: R,
+        compress: Compress,
+        validate: Validate,
+    ) -> Result<Self, SerializationError> {
+        let affine = G1Affine::deserialize_with_mode(reader, compress, validate)?;
+        Ok(affine.into())
+    }
+}
+
+impl Valid for G2Point {
+    fn check(&self) -> Result<(), SerializationError> {
+        G2Affine::try_from(G2Point {
+            x0: self.x0,
+            x1: self.x1,
+            y0: self.y0,
+            y1: self.y1,
+        })
+        .map(|_| ())
+        .map_err(|_| SerializationError::InvalidData)
+    }
+}
+
+impl CanonicalSerialize for G2Point {
+    fn serialize_with_mode<W: Write>(
+        &self,
+        writer: W,
+        compress: Compress,
+    ) -> Result<(), SerializationError> {
+        let affine = G2Affine::try_from(G2Point {
+            x0: self.x0,
+            x1: self.x1,
+            y0: self.y0,
+            y1: self.y1,
+        })
+        .map_err(|_| SerializationError::InvalidData)?;
+        affine.serialize_with_mode(writer, compress)
+    }
+
+    fn serialized_size(&self, compress: Compress) -> usize {
+        G2Affine::default().serialized_size(compress)
+    }
+}
+
+impl CanonicalDeserialize for G2Point {
+    fn deserialize_with_mode<R: Read>(
+        reader: R,
+        compress: Compress,
+        validate: Validate,
+    ) -> Result<Self, SerializationError> {
+        let affine = G2Affine::deserialize_with_mode(reader, compress, validate)?;
+        Ok(affine.into())
+    }
+}
@@ -1,16 +1,19 @@
 use alloy::{
     hex::{self, ToHexExt},
-    primitives::U256,
+    primitives::{Bytes, FixedBytes, U256},
     sol_types::SolValue,
 };
-use ark_bn254::{Bn254, Fq, Fr, G1Affine, G2Affine};
+use ark_bn254::{Bn254, Fq, Fq2, Fr, G1Affine, G2Affine};
 use ark_ec::{pairing::Pairing, short_weierstrass::SWCurveConfig, AffineRepr, CurveGroup, Group};
 use ark_ff::{Field, PrimeField};
 use ark_std::{
-    rand::{rngs::StdRng, SeedableRng},
+    rand::{rngs::StdRng, Rng, SeedableRng},
     test_rng, UniformRand,
 };
-use bn254_contract_adapter::{field_to_u256, u256_to_field, G1Point, G2Point};
+use bn254_contract_adapter::{
+    compress_g1, compress_g2, decompress_g1, decompress_g2, field_to_u256, msm_window_bits,
+    pippenger_msm, pippenger_msm_with_window, u256_to_field, G1Point, G2Point,
+};
 use clap::{Parser, ValueEnum};
 
 #[derive(Parser)]
@@ -36,6 +39,9 @@ enum Action {
     Bn254PairingProd2,
     /// Generate bases and scalars for MSM computation
     Bn254MSM,
+    /// Generate bases and scalars for MSM computation, dumping the
+    /// bucket-method reference's per-window bucket sums
+    Bn254MSMBuckets,
     /// Compute inverse op in the scalar field
     Bn254ScalarInvOp,
     /// Compute negate op in the scalar field
@@ -46,10 +52,31 @@ enum Action {
     Bn254G1NegOp,
     /// Compute quadratic residue in base field
     Bn254Qr,
+    /// Compress a random G1 point to its 32-byte form
+    Bn254G1Compress,
+    /// Compress a random G2 point to its 64-byte form
+    Bn254G2Compress,
+    /// Test the prime-order subgroup check for a G2 point
+    Bn254G2SubgroupCheck,
     /// Test only logic
     TestOnly,
 }
 
+/// Sample a point on the full BN254 G2 curve `E(Fq2)`, which has a
+/// nontrivial cofactor over the prime-order subgroup. Unlike
+/// `G2Affine::rand`, the result is generically *not* in the prime-order
+/// subgroup, which is useful for exercising the negative case of a subgroup
+/// check.
+fn random_g2_on_curve(rng: &mut impl Rng) -> G2Affine {
+    loop {
+        let x = Fq2::rand(rng);
+        let y2 = x * x * x + <ark_bn254::g2::Config as SWCurveConfig>::COEFF_B;
+        if let Some(y) = y2.sqrt() {
+            return G2Affine::new_unchecked(x, y);
+        }
+    }
+}
+
 fn main() {
     let cli = Cli::parse();
     match cli.action {
@@ -126,6 +153,9 @@ fn main() {
             }
 
             let prod = ark_bn254::g1::Config::msm(&bases, &scalars).unwrap();
+            // cross-check against our own deterministic bucket-method reference
+            assert_eq!(pippenger_msm(&bases, &scalars), prod.into_affine());
+
             let parsed_bases: Vec<G1Point> = bases.iter().map(|b| (*b).into()).collect();
             let parsed_scalars: Vec<U256> = scalars.iter().map(|s| field_to_u256(*s)).collect();
             let parsed_prod: G1Point = prod.into_affine().into();
@@ -133,6 +163,41 @@ fn main() {
             let res = (parsed_bases, parsed_scalars, parsed_prod);
             println!("{}", res.abi_encode_params().encode_hex());
         }
+        Action::Bn254MSMBuckets => {
+            if cli.args.len() != 1 {
+                panic!("Should provide arg1=numBases");
+            }
+
+            let num_bases = cli.args[0].parse::<u64>().unwrap();
+            let mut rng = test_rng();
+            let mut bases = vec![];
+            let mut scalars = vec![];
+
+            for _ in 0..num_bases {
+                bases.push(G1Affine::rand(&mut rng));
+                scalars.push(Fr::rand(&mut rng));
+            }
+
+            let c = msm_window_bits(bases.len());
+            let (prod, bucket_sums) = pippenger_msm_with_window(&bases, &scalars, c);
+
+            let parsed_bases: Vec<G1Point> = bases.iter().map(|b| (*b).into()).collect();
+            let parsed_scalars: Vec<U256> = scalars.iter().map(|s| field_to_u256(*s)).collect();
+            let parsed_prod: G1Point = prod.into();
+            let parsed_buckets: Vec<Vec<G1Point>> = bucket_sums
+                .iter()
+                .map(|window| window.iter().map(|b| (*b).into()).collect())
+                .collect();
+
+            let res = (
+                parsed_bases,
+                parsed_scalars,
+                parsed_prod,
+                U256::from(c),
+                parsed_buckets,
+            );
+            println!("{}", res.abi_encode_params().encode_hex());
+        }
         Action::Bn254ScalarInvOp => {
             if cli.args.len() != 1 {
                 panic!("Should provide arg1=scalar");
@@ -208,6 +273,62 @@ fn main() {
             let a_sol = field_to_u256(a);
             println!("{}", (x_sol, a_sol, is_qr).abi_encode_params().encode_hex());
         }
+        Action::Bn254G1Compress => {
+            if cli.args.len() != 1 {
+                panic!("Should provide arg1=seed");
+            }
+            let seed = cli.args[0].parse::<u64>().unwrap();
+            let rng = &mut StdRng::seed_from_u64(seed);
+
+            let p = G1Affine::rand(rng);
+            let compressed = compress_g1(p);
+            assert_eq!(decompress_g1(compressed), p);
+
+            let p_sol: G1Point = p.into();
+            let compressed_sol = FixedBytes::<32>::from(compressed);
+            println!(
+                "{}",
+                (p_sol, compressed_sol).abi_encode_params().encode_hex()
+            );
+        }
+        Action::Bn254G2Compress => {
+            if cli.args.len() != 1 {
+                panic!("Should provide arg1=seed");
+            }
+            let seed = cli.args[0].parse::<u64>().unwrap();
+            let rng = &mut StdRng::seed_from_u64(seed);
+
+            let p = G2Affine::rand(rng);
+            let compressed = compress_g2(p);
+            assert_eq!(decompress_g2(compressed), p);
+
+            let p_sol: G2Point = p.into();
+            let compressed_sol = Bytes::copy_from_slice(&compressed);
+            println!(
+                "{}",
+                (p_sol, compressed_sol).abi_encode_params().encode_hex()
+            );
+        }
+        Action::Bn254G2SubgroupCheck => {
+            if cli.args.len() != 1 {
+                panic!("Should provide arg1=seed");
+            }
+            let seed = cli.args[0].parse::<u64>().unwrap();
+            let mut rng = StdRng::seed_from_u64(seed);
+
+            // even seeds: a genuine subgroup element; odd seeds: a point
+            // that's on curve but (generically) outside the prime-order
+            // subgroup, to exercise both branches of the check
+            let p = if seed % 2 == 0 {
+                G2Affine::rand(&mut rng)
+            } else {
+                random_g2_on_curve(&mut rng)
+            };
+            let in_subgroup = p.is_in_correct_subgroup_assuming_on_curve();
+
+            let p_sol: G2Point = p.into();
+            println!("{}", (p_sol, in_subgroup).abi_encode_params().encode_hex());
+        }
         Action::TestOnly => {
             eprintln!("test only");
         }